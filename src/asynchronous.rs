@@ -0,0 +1,349 @@
+//! Asynchronous SNMPv2 client built on Tokio.
+//!
+//! Mirrors [`crate::sync::SyncSession`] but drives the socket through
+//! `tokio::net::UdpSocket` so many sessions can be polled concurrently from
+//! a single task. Only available when the `tokio` feature is enabled, so the
+//! synchronous path stays free of the extra dependency.
+#![cfg(feature = "tokio")]
+
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs},
+    num::Wrapping,
+    time::Duration,
+};
+
+use tokio::net::UdpSocket;
+
+use crate::{
+    handle_response, pdu, sync::DiscoveryMode, ResponseItem, ResponseItemInt, SnmpError,
+    SnmpMessageType, SnmpPdu, SnmpResult, Value, BUFFER_SIZE,
+};
+
+/// Builder for [`AsyncSession`].
+pub struct AsyncSessionBuilder<A, S> {
+    destination: A,
+    community: Option<S>,
+    timeout: Option<Duration>,
+    req_id: i32,
+}
+
+impl<A, S> AsyncSessionBuilder<A, S>
+where
+    A: ToSocketAddrs,
+    S: AsRef<[u8]>,
+{
+    pub fn community(mut self, community: S) -> Self {
+        self.community = Some(community);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn req_id(mut self, req_id: i32) -> Self {
+        self.req_id = req_id;
+        self
+    }
+
+    pub async fn build(self) -> io::Result<AsyncSession> {
+        AsyncSession::new(self.destination, self.community, self.timeout, self.req_id).await
+    }
+}
+
+/// Asynchronous SNMPv2 client.
+pub struct AsyncSession {
+    destination: SocketAddr, // example: IPv4(127.0.0.1:161) or IPv6(_)
+    socket: UdpSocket,
+    community: Vec<u8>,
+    timeout: Option<Duration>,
+    req_id: Wrapping<i32>,
+    send_pdu: pdu::Buf,
+}
+
+impl AsyncSession {
+    pub fn builder<A, S>(destination: A) -> AsyncSessionBuilder<A, S>
+    where
+        S: AsRef<[u8]>,
+    {
+        AsyncSessionBuilder {
+            destination,
+            community: None,
+            timeout: None,
+            req_id: 0,
+        }
+    }
+
+    async fn new<SA, T>(
+        destination: SA,
+        community: Option<T>,
+        timeout: Option<Duration>,
+        starting_req_id: i32,
+    ) -> io::Result<Self>
+    where
+        SA: ToSocketAddrs,
+        T: AsRef<[u8]>,
+    {
+        let destination_out: SocketAddr = destination
+            .to_socket_addrs()?
+            .next()
+            .expect("empty list of socket addrs");
+
+        let socket = Self::create_socket(destination_out).await?;
+
+        let community = community
+            .map(|c| c.as_ref().into())
+            .unwrap_or_else(|| b"public".to_vec());
+
+        Ok(AsyncSession {
+            destination: destination_out,
+            socket,
+            community,
+            timeout,
+            req_id: Wrapping(starting_req_id),
+            send_pdu: pdu::Buf::default(),
+        })
+    }
+
+    async fn create_socket(sock_addr: SocketAddr) -> io::Result<UdpSocket> {
+        match sock_addr {
+            SocketAddr::V4(_) => UdpSocket::bind((Ipv4Addr::new(0, 0, 0, 0), 0)).await,
+            SocketAddr::V6(_) => UdpSocket::bind((Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0), 0)).await,
+        }
+    }
+
+    async fn create_discovery_socket(
+        sock_addr: SocketAddr,
+        mode: &DiscoveryMode,
+    ) -> io::Result<UdpSocket> {
+        let socket = Self::create_socket(sock_addr).await?;
+
+        match *mode {
+            DiscoveryMode::Broadcast => socket.set_broadcast(true)?,
+            DiscoveryMode::MulticastV4(group) => {
+                socket.join_multicast_v4(group, Ipv4Addr::UNSPECIFIED)?
+            }
+            DiscoveryMode::MulticastV6(group, interface) => {
+                socket.join_multicast_v6(&group, interface)?
+            }
+        }
+
+        Ok(socket)
+    }
+
+    async fn send_and_recv(&self, pdu: &pdu::Buf) -> SnmpResult<ResponseItemInt> {
+        if self.socket.send_to(&pdu[..], self.destination).await.is_ok() {
+            Self::recv_one(&self.socket, self.timeout).await
+        } else {
+            Err(SnmpError::SendError)
+        }
+    }
+
+    async fn recv_one(socket: &UdpSocket, timeout: Option<Duration>) -> SnmpResult<ResponseItemInt> {
+        let mut buf_out = vec![0u8; BUFFER_SIZE];
+
+        let recv = socket.recv_from(&mut buf_out[..]);
+        let result = match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, recv)
+                .await
+                .map_err(|_| SnmpError::ReceiveError)?,
+            None => recv.await,
+        };
+
+        if let Ok((size, src_addr)) = result {
+            buf_out.truncate(size);
+            Ok(ResponseItemInt {
+                address: src_addr.ip().to_string(),
+                data: buf_out,
+            })
+        } else {
+            Err(SnmpError::ReceiveError)
+        }
+    }
+
+    pub async fn get<T>(&mut self, names: &[T]) -> SnmpResult<SnmpPdu>
+    where
+        T: AsRef<[u32]>,
+    {
+        let req_id = self.req_id.0;
+        self.req_id += Wrapping(1);
+
+        pdu::build_get(self.community.as_slice(), req_id, names, &mut self.send_pdu)?;
+
+        let response = self.send_and_recv(&self.send_pdu).await?;
+
+        handle_response(req_id, self.community.as_slice(), response.data.as_slice())
+    }
+
+    pub async fn getnext(&mut self, name: &[u32]) -> SnmpResult<SnmpPdu> {
+        let req_id = self.req_id.0;
+        self.req_id += Wrapping(1);
+
+        pdu::build_getnext(self.community.as_slice(), req_id, name, &mut self.send_pdu)?;
+
+        let response = self.send_and_recv(&self.send_pdu).await?;
+
+        handle_response(req_id, self.community.as_slice(), response.data.as_slice())
+    }
+
+    pub async fn get_all_responses<T>(
+        &mut self,
+        names: &[T],
+        timeout: Duration,
+    ) -> SnmpResult<Vec<ResponseItem>>
+    where
+        T: AsRef<[u32]>,
+    {
+        self.get_all_responses_with_mode(names, timeout, DiscoveryMode::Broadcast)
+            .await
+    }
+
+    /// Like [`get_all_responses`](Self::get_all_responses), but lets the
+    /// caller pick how the single request is fanned out to multiple agents
+    /// via `mode` instead of always broadcasting. See [`DiscoveryMode`] for
+    /// why discovery on IPv6 needs one of the multicast modes.
+    pub async fn get_all_responses_with_mode<T>(
+        &mut self,
+        names: &[T],
+        timeout: Duration,
+        mode: DiscoveryMode,
+    ) -> SnmpResult<Vec<ResponseItem>>
+    where
+        T: AsRef<[u32]>,
+    {
+        let req_id = self.req_id.0;
+        self.req_id += Wrapping(1);
+
+        let socket = Self::create_discovery_socket(self.destination, &mode)
+            .await
+            .map_err(|_| SnmpError::SocketError)?;
+
+        // A multicast mode sends to the group address, not the session's
+        // configured destination; the port stays the one the session was
+        // built with.
+        let send_to = match mode {
+            DiscoveryMode::Broadcast => self.destination,
+            DiscoveryMode::MulticastV4(group) => {
+                SocketAddr::new(IpAddr::V4(group), self.destination.port())
+            }
+            DiscoveryMode::MulticastV6(group, _) => {
+                SocketAddr::new(IpAddr::V6(group), self.destination.port())
+            }
+        };
+
+        // send
+        pdu::build_get(self.community.as_slice(), req_id, names, &mut self.send_pdu)?;
+        socket
+            .send_to(&self.send_pdu[..], send_to)
+            .await
+            .map_err(|_| SnmpError::SendError)?;
+
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        // recv all responses
+        let mut vec1: Vec<ResponseItemInt> = Vec::new();
+        loop {
+            let remaining = match deadline.checked_duration_since(tokio::time::Instant::now()) {
+                Some(remaining) if remaining > Duration::ZERO => remaining,
+                _ => break,
+            };
+            match Self::recv_one(&socket, Some(remaining)).await {
+                Ok(item) => vec1.push(item),
+                Err(_) => break,
+            }
+        }
+
+        // parsing to SnmpPdu
+        let mut vec2: Vec<ResponseItem> = Vec::new();
+        for item in vec1.iter() {
+            let r1 = handle_response(req_id, self.community.as_slice(), item.data.as_slice());
+            if let Ok(data) = r1 {
+                vec2.push(ResponseItem {
+                    address: item.address.clone(),
+                    data,
+                })
+            } else {
+                // Error in response! - skip!
+            }
+        }
+
+        match mode {
+            DiscoveryMode::Broadcast => {}
+            DiscoveryMode::MulticastV4(group) => {
+                let _ = socket.leave_multicast_v4(group, Ipv4Addr::UNSPECIFIED);
+            }
+            DiscoveryMode::MulticastV6(group, interface) => {
+                let _ = socket.leave_multicast_v6(&group, interface);
+            }
+        }
+
+        Ok(vec2)
+    }
+
+    pub async fn getbulk<T>(
+        &mut self,
+        names: &[T],
+        non_repeaters: u32,
+        max_repetitions: u32,
+    ) -> SnmpResult<SnmpPdu>
+    where
+        T: AsRef<[u32]>,
+    {
+        let req_id = self.req_id.0;
+        self.req_id += Wrapping(1);
+
+        pdu::build_getbulk(
+            self.community.as_slice(),
+            req_id,
+            names,
+            non_repeaters,
+            max_repetitions,
+            &mut self.send_pdu,
+        )?;
+
+        let response = self.send_and_recv(&self.send_pdu).await?;
+
+        handle_response(req_id, self.community.as_slice(), response.data.as_slice())
+    }
+
+    /// # Panics if any of the values are not one of these supported types:
+    ///   - `Boolean`
+    ///   - `Null`
+    ///   - `Integer`
+    ///   - `OctetString`
+    ///   - `ObjectIdentifier`
+    ///   - `IpAddress`
+    ///   - `Counter32`
+    ///   - `Unsigned32`
+    ///   - `Timeticks`
+    ///   - `Opaque`
+    ///   - `Counter64`
+    pub async fn set(&mut self, values: &[(&[u32], Value)]) -> SnmpResult<SnmpPdu> {
+        let req_id = self.req_id.0;
+        self.req_id += Wrapping(1);
+
+        pdu::build_set(
+            self.community.as_slice(),
+            req_id,
+            values,
+            &mut self.send_pdu,
+        )?;
+
+        let response = self.send_and_recv(&self.send_pdu).await?;
+        let pdu_bytes = &response.data;
+
+        let resp = SnmpPdu::from_bytes(pdu_bytes)?;
+        if resp.message_type != SnmpMessageType::Response {
+            return Err(SnmpError::AsnWrongType);
+        }
+        if resp.req_id != req_id {
+            return Err(SnmpError::RequestIdMismatch);
+        }
+        if resp.community != self.community {
+            return Err(SnmpError::CommunityMismatch);
+        }
+        Ok(resp)
+    }
+}
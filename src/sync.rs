@@ -1,6 +1,7 @@
 use std::{
+    collections::VecDeque,
     io,
-    net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket},
     num::Wrapping,
     time::{Duration, Instant},
 };
@@ -16,6 +17,8 @@ pub struct SyncSessionBuilder<A, S> {
     community: Option<S>,
     timeout: Option<Duration>,
     req_id: i32,
+    retries: u32,
+    backoff: f64,
 }
 
 impl<A, S> SyncSessionBuilder<A, S>
@@ -38,16 +41,63 @@ where
         self
     }
 
+    /// Number of retransmissions to attempt after a receive timeout before
+    /// giving up with `SnmpError::ReceiveError`. Defaults to `0` (send
+    /// once, same as before this was added).
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Multiplier applied to the read timeout before each retransmission,
+    /// e.g. `2.0` doubles it every attempt. Defaults to `1.0` (unchanged
+    /// timeout on every retry). Values that are not finite and positive
+    /// (e.g. `0.0`, negative, NaN) are ignored and fall back to `1.0`.
+    pub fn backoff(mut self, backoff: f64) -> Self {
+        self.backoff = if backoff.is_finite() && backoff > 0.0 {
+            backoff
+        } else {
+            1.0
+        };
+        self
+    }
+
     pub fn build(self) -> io::Result<SyncSession> {
-        SyncSession::new(self.destination, self.community, self.timeout, self.req_id)
+        SyncSession::new(
+            self.destination,
+            self.community,
+            self.timeout,
+            self.req_id,
+            self.retries,
+            self.backoff,
+        )
     }
 }
 
+/// How `get_all_responses` fans its single request out to multiple agents.
+///
+/// IPv4 has no routable multicast equivalent in common use for SNMP
+/// discovery, so `Broadcast` remains the default there; IPv6 has no
+/// broadcast at all, so discovery on v6 networks requires one of the
+/// multicast variants instead.
+pub enum DiscoveryMode {
+    /// IPv4 limited/subnet broadcast (the original behavior).
+    Broadcast,
+    /// Join the given IPv4 multicast group before sending.
+    MulticastV4(Ipv4Addr),
+    /// Join the given IPv6 multicast group on the interface with the given
+    /// index before sending.
+    MulticastV6(Ipv6Addr, u32),
+}
+
 /// Synchronous SNMPv2 client.
 pub struct SyncSession {
     destination: SocketAddr, // example: IPv4(127.0.0.1:161) or IPv6(_)
     socket: UdpSocket,
     community: Vec<u8>,
+    timeout: Option<Duration>,
+    retries: u32,
+    backoff: f64,
     req_id: Wrapping<i32>,
     send_pdu: pdu::Buf,
 }
@@ -62,6 +112,8 @@ impl SyncSession {
             community: None,
             timeout: None,
             req_id: 0,
+            retries: 0,
+            backoff: 1.0,
         }
     }
 
@@ -70,6 +122,8 @@ impl SyncSession {
         community: Option<T>,
         timeout: Option<Duration>,
         starting_req_id: i32,
+        retries: u32,
+        backoff: f64,
     ) -> io::Result<Self>
     where
         SA: ToSocketAddrs,
@@ -90,6 +144,9 @@ impl SyncSession {
             destination: destination_out,
             socket,
             community,
+            timeout,
+            retries,
+            backoff,
             req_id: Wrapping(starting_req_id),
             send_pdu: pdu::Buf::default(),
         })
@@ -109,12 +166,53 @@ impl SyncSession {
         Ok(socket)
     }
 
+    fn create_discovery_socket(
+        sock_addr: SocketAddr,
+        timeout: Duration,
+        mode: &DiscoveryMode,
+    ) -> io::Result<UdpSocket> {
+        let broadcast = matches!(mode, DiscoveryMode::Broadcast);
+        let socket = Self::create_socket(sock_addr, Some(timeout), broadcast)?;
+
+        match *mode {
+            DiscoveryMode::Broadcast => {}
+            DiscoveryMode::MulticastV4(group) => {
+                socket.join_multicast_v4(&group, &Ipv4Addr::UNSPECIFIED)?
+            }
+            DiscoveryMode::MulticastV6(group, interface) => {
+                socket.join_multicast_v6(&group, interface)?
+            }
+        }
+
+        Ok(socket)
+    }
+
     fn send_and_recv(&self, pdu: &pdu::Buf) -> SnmpResult<ResponseItemInt> {
-        if let Ok(_pdu_len) = self.socket.send_to(&pdu[..], self.destination) {
-            Self::recv_one(&self.socket)
-        } else {
-            Err(SnmpError::SendError)
+        let mut timeout = self.timeout;
+        self.socket
+            .set_read_timeout(timeout)
+            .map_err(|_| SnmpError::SocketError)?;
+
+        for attempt in 0..=self.retries {
+            if attempt > 0 {
+                timeout = timeout.map(|t| apply_backoff(t, self.backoff));
+                self.socket
+                    .set_read_timeout(timeout)
+                    .map_err(|_| SnmpError::SocketError)?;
+            }
+
+            if self.socket.send_to(&pdu[..], self.destination).is_err() {
+                return Err(SnmpError::SendError);
+            }
+
+            match Self::recv_one(&self.socket) {
+                Ok(response) => return Ok(response),
+                Err(e) if attempt == self.retries => return Err(e),
+                Err(_) => continue,
+            }
         }
+
+        unreachable!("loop always returns on its last iteration")
     }
 
     fn recv_one(socket: &UdpSocket) -> SnmpResult<ResponseItemInt> {
@@ -163,19 +261,47 @@ impl SyncSession {
         names: &[T],
         timeout: Duration,
     ) -> SnmpResult<Vec<ResponseItem>>
+    where
+        T: AsRef<[u32]>,
+    {
+        self.get_all_responses_with_mode(names, timeout, DiscoveryMode::Broadcast)
+    }
+
+    /// Like [`get_all_responses`](Self::get_all_responses), but lets the
+    /// caller pick how the single request is fanned out to multiple agents
+    /// via `mode` instead of always broadcasting.
+    pub fn get_all_responses_with_mode<T>(
+        &mut self,
+        names: &[T],
+        timeout: Duration,
+        mode: DiscoveryMode,
+    ) -> SnmpResult<Vec<ResponseItem>>
     where
         T: AsRef<[u32]>,
     {
         let req_id = self.req_id.0;
         self.req_id += Wrapping(1);
 
-        let socket = Self::create_socket(self.destination, Some(timeout), true)
+        let socket = Self::create_discovery_socket(self.destination, timeout, &mode)
             .map_err(|_| SnmpError::SocketError)?;
 
+        // A multicast mode sends to the group address, not the session's
+        // configured destination; the port stays the one the session was
+        // built with.
+        let send_to = match mode {
+            DiscoveryMode::Broadcast => self.destination,
+            DiscoveryMode::MulticastV4(group) => {
+                SocketAddr::new(IpAddr::V4(group), self.destination.port())
+            }
+            DiscoveryMode::MulticastV6(group, _) => {
+                SocketAddr::new(IpAddr::V6(group), self.destination.port())
+            }
+        };
+
         // send
         pdu::build_get(self.community.as_slice(), req_id, names, &mut self.send_pdu)?;
         socket
-            .send_to(&self.send_pdu[..], self.destination)
+            .send_to(&self.send_pdu[..], send_to)
             .map_err(|_| SnmpError::SendError)?;
 
         let ts1 = Instant::now();
@@ -205,6 +331,16 @@ impl SyncSession {
             }
         }
 
+        match mode {
+            DiscoveryMode::Broadcast => {}
+            DiscoveryMode::MulticastV4(group) => {
+                let _ = socket.leave_multicast_v4(&group, &Ipv4Addr::UNSPECIFIED);
+            }
+            DiscoveryMode::MulticastV6(group, interface) => {
+                let _ = socket.leave_multicast_v6(&group, interface);
+            }
+        }
+
         Ok(vec2)
     }
 
@@ -272,4 +408,138 @@ impl SyncSession {
         }
         Ok(resp)
     }
+
+    /// Walks the subtree rooted at `root`, yielding each varbind lazily.
+    ///
+    /// Internally this repeatedly issues `getbulk` (falling back to
+    /// `getnext` if the agent rejects bulk requests), buffering the
+    /// varbinds each response carries. It stops once a returned OID is no
+    /// longer a descendant of `root`, or the agent signals the end of the
+    /// subtree with `NoSuchObject`/`NoSuchInstance`/`endOfMibView`.
+    pub fn walk(&mut self, root: &[u32]) -> impl Iterator<Item = SnmpResult<(Vec<u32>, Value)>> + '_ {
+        TableWalk {
+            session: self,
+            root: root.to_vec(),
+            next_oid: root.to_vec(),
+            buffer: VecDeque::new(),
+            bulk_supported: true,
+            done: false,
+        }
+    }
+}
+
+const WALK_MAX_REPETITIONS: u32 = 10;
+
+fn is_descendant(root: &[u32], oid: &[u32]) -> bool {
+    oid.len() > root.len() && oid[..root.len()] == *root
+}
+
+/// Scales `timeout` by `factor`, saturating to `Duration::MAX` instead of
+/// panicking if the result would overflow (e.g. a large backoff multiplier
+/// combined with many retries). `factor` is expected to already be a
+/// finite, positive number (see `SyncSessionBuilder::backoff`).
+fn apply_backoff(timeout: Duration, factor: f64) -> Duration {
+    Duration::try_from_secs_f64(timeout.as_secs_f64() * factor).unwrap_or(Duration::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_descendant_recognizes_strict_descendants_only() {
+        assert!(is_descendant(&[1, 3, 6], &[1, 3, 6, 1]));
+        assert!(!is_descendant(&[1, 3, 6], &[1, 3, 6]));
+        assert!(!is_descendant(&[1, 3, 6], &[1, 3, 7]));
+        assert!(!is_descendant(&[1, 3, 6], &[1, 3]));
+    }
+
+    #[test]
+    fn apply_backoff_never_panics_on_common_inputs() {
+        for factor in [0.5, 1.0, 2.0, 10.0] {
+            let mut timeout = Duration::from_millis(100);
+            for _ in 0..100 {
+                timeout = apply_backoff(timeout, factor);
+            }
+        }
+    }
+}
+
+struct TableWalk<'a> {
+    session: &'a mut SyncSession,
+    root: Vec<u32>,
+    next_oid: Vec<u32>,
+    buffer: VecDeque<(Vec<u32>, Value)>,
+    // Once a `getbulk` fails we assume the agent doesn't support it at all
+    // and stick to `getnext` for the rest of the walk, instead of paying a
+    // full getbulk round trip (and its retry budget) on every step.
+    bulk_supported: bool,
+    done: bool,
+}
+
+impl<'a> Iterator for TableWalk<'a> {
+    type Item = SnmpResult<(Vec<u32>, Value)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((oid, value)) = self.buffer.pop_front() {
+                if !is_descendant(&self.root, &oid) {
+                    self.done = true;
+                    return None;
+                }
+                return match value {
+                    Value::NoSuchObject | Value::NoSuchInstance | Value::EndOfMibView => {
+                        self.done = true;
+                        None
+                    }
+                    _ => {
+                        self.next_oid = oid.clone();
+                        Some(Ok((oid, value)))
+                    }
+                };
+            }
+
+            if self.done {
+                return None;
+            }
+
+            let pdu = if self.bulk_supported {
+                match self
+                    .session
+                    .getbulk(&[self.next_oid.as_slice()], 0, WALK_MAX_REPETITIONS)
+                {
+                    Ok(pdu) => pdu,
+                    Err(_) => {
+                        self.bulk_supported = false;
+                        match self.session.getnext(&self.next_oid) {
+                            Ok(pdu) => pdu,
+                            Err(e) => {
+                                self.done = true;
+                                return Some(Err(e));
+                            }
+                        }
+                    }
+                }
+            } else {
+                match self.session.getnext(&self.next_oid) {
+                    Ok(pdu) => pdu,
+                    Err(e) => {
+                        self.done = true;
+                        return Some(Err(e));
+                    }
+                }
+            };
+
+            let mut received_any = false;
+            for (oid, value) in pdu.varbinds {
+                received_any = true;
+                self.buffer.push_back((oid.iter().collect(), value));
+            }
+
+            if !received_any {
+                self.done = true;
+                return None;
+            }
+        }
+    }
 }